@@ -1,6 +1,6 @@
 use crate::DirBuilder;
 use crate::DirInfo;
-use chrono::{DateTime, Local, LocalResult, TimeZone, Utc};
+use chrono::{DateTime, FixedOffset, Local, LocalResult, TimeZone, Utc};
 use nu_engine::env::current_dir;
 use nu_engine::CallExt;
 use nu_glob::MatchOptions;
@@ -13,12 +13,24 @@ use nu_protocol::{
 };
 use pathdiff::diff_paths;
 
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::Read;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// The timezone `ls` renders its time columns in. The internal conversion
+/// always goes to `Utc` first; this is applied only at the end,
+/// right before the value is handed to `Value::Date`.
+enum LsTimeZone {
+    Local,
+    Utc,
+    Named(chrono_tz::Tz),
+}
+
 #[derive(Clone)]
 pub struct Ls;
 
@@ -63,7 +75,32 @@ impl Command for Ls {
                 Some('D'),
             )
             .switch("git", "Display the git status of files", Some('g'))
+            .switch(
+                "git-no-ignored",
+                "When displaying git status, exclude ignored files from the scan",
+                None,
+            )
+            .switch(
+                "git-recurse-untracked",
+                "When displaying git status, recurse into untracked directories instead of reporting them as a single entry",
+                None,
+            )
+            .switch(
+                "git-renames",
+                "When displaying git status, detect renames between HEAD/index and index/workdir",
+                None,
+            )
             .switch("mime-type", "Show mime-type in type column", Some('m'))
+            .switch(
+                "utc",
+                "Render time columns (created/accessed/modified/changed) in UTC instead of the local timezone",
+                None,
+            )
+            .switch(
+                "full",
+                "In --long mode, also show extended stat columns (octal mode, device, blocks; on Windows, decoded attributes only for entries whose metadata could not be read)",
+                None,
+            )
             .category(Category::FileSystem)
     }
 
@@ -80,8 +117,27 @@ impl Command for Ls {
         let full_paths = call.has_flag("full-paths");
         let du = call.has_flag("du");
         let git = call.has_flag("git");
+        let git_status_options = GitStatusOptions {
+            include_ignored: !call.has_flag("git-no-ignored"),
+            recurse_untracked_dirs: call.has_flag("git-recurse-untracked"),
+            detect_renames: call.has_flag("git-renames"),
+        };
         let directory = call.has_flag("directory");
         let use_mime_type = call.has_flag("mime-type");
+        let full = call.has_flag("full");
+        let time_zone = if call.has_flag("utc") {
+            LsTimeZone::Utc
+        } else if let Some(zone_name) = stack
+            .get_env_var(engine_state, "NU_LS_TIMEZONE")
+            .and_then(|v| v.as_string().ok())
+        {
+            zone_name
+                .parse::<chrono_tz::Tz>()
+                .map(LsTimeZone::Named)
+                .unwrap_or(LsTimeZone::Local)
+        } else {
+            LsTimeZone::Local
+        };
         let ctrl_c = engine_state.ctrlc.clone();
         let call_span = call.head;
         let cwd = current_dir(engine_state, stack)?;
@@ -179,6 +235,12 @@ impl Command for Ls {
 
         let mut hidden_dirs = vec![];
 
+        let git_cache = if git {
+            Some(GitCache::new(git_status_options))
+        } else {
+            None
+        };
+
         Ok(paths_peek
             .into_iter()
             .filter_map(move |x| match x {
@@ -254,8 +316,10 @@ impl Command for Ls {
                                 long,
                                 du,
                                 ctrl_c.clone(),
-                                git,
+                                git_cache.as_ref(),
                                 use_mime_type,
+                                &time_zone,
+                                full,
                             );
                             match entry {
                                 Ok(value) => Some(value),
@@ -312,6 +376,11 @@ impl Command for Ls {
                 example: "ls -ag | where git_status == untracked",
                 result: None,
             },
+            Example {
+                description: "List files whose staged change is a modification",
+                example: "ls -gl | where git_staged == modified",
+                result: None,
+            },
             Example {
                 description: "List all dirs in your home directory",
                 example: "ls -a ~ | where type == dir",
@@ -328,6 +397,16 @@ impl Command for Ls {
                 example: "['/path/to/directory' '/path/to/file'] | each { ls -D $in } | flatten",
                 result: None,
             },
+            Example {
+                description: "List files with their time columns rendered in UTC, for reproducible output across machines",
+                example: "ls -l --utc",
+                result: None,
+            },
+            Example {
+                description: "List files with extended stat columns such as mode, device and blocks",
+                example: "ls -l --full",
+                result: None,
+            },
         ]
     }
 }
@@ -381,7 +460,12 @@ use std::os::unix::fs::FileTypeExt;
 use std::path::Path;
 use std::sync::atomic::AtomicBool;
 
-pub fn get_file_type(md: &std::fs::Metadata, display_name: &str, use_mime_type: bool) -> String {
+pub fn get_file_type(
+    path: &Path,
+    md: &std::fs::Metadata,
+    display_name: &str,
+    use_mime_type: bool,
+) -> String {
     let ft = md.file_type();
     let mut file_type: String = String::from("unknown");
     if ft.is_dir() {
@@ -406,10 +490,21 @@ pub fn get_file_type(md: &std::fs::Metadata, display_name: &str, use_mime_type:
     }
     if use_mime_type {
         let guess = mime_guess::from_path(display_name);
-        let mime_guess = match guess.first() {
-            Some(mime_type) => mime_type.essence_str().to_string(),
-            None => "unknown".to_string(),
-        };
+        let mime_guess = guess
+            .first()
+            .map(|mime_type| mime_type.essence_str().to_string())
+            .or_else(|| {
+                // The extension didn't tell us anything useful; fall back to
+                // sniffing the file's magic bytes. Only worth doing for regular,
+                // non-empty files -- directories, symlinks, and devices don't
+                // have content to sniff.
+                if file_type == "file" && md.len() > 0 {
+                    sniff_mime_type(path)
+                } else {
+                    None
+                }
+            });
+        let mime_guess = mime_guess.unwrap_or_else(|| "unknown".to_string());
         if file_type == "file" {
             mime_guess
         } else {
@@ -420,13 +515,51 @@ pub fn get_file_type(md: &std::fs::Metadata, display_name: &str, use_mime_type:
     }
 }
 
+/// Magic-byte signatures for common file formats, used as a fallback when
+/// extension-based MIME guessing can't classify a file -- extensionless
+/// scripts, binaries, and archives are common and otherwise all report as
+/// "unknown".
+fn sniff_mime_type(path: &Path) -> Option<String> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (&[0xFF, 0xD8, 0xFF], "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"BM", "image/bmp"),
+        (b"%PDF", "application/pdf"),
+        (&[0x7F, b'E', b'L', b'F'], "application/x-elf"),
+        (b"MZ", "application/x-msdownload"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"PK\x05\x06", "application/zip"),
+        (&[0x1F, 0x8B], "application/gzip"),
+        (b"BZh", "application/x-bzip2"),
+        (b"7z\xBC\xAF\x27\x1C", "application/x-7z-compressed"),
+        (b"Rar!\x1a\x07", "application/vnd.rar"),
+        (b"OggS", "audio/ogg"),
+        (b"fLaC", "audio/flac"),
+        (b"ID3", "audio/mpeg"),
+        (&[0x1A, 0x45, 0xDF, 0xA3], "video/webm"),
+    ];
+
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut buf = [0u8; 4096];
+    let n = file.read(&mut buf).ok()?;
+    let buf = &buf[..n];
+
+    SIGNATURES
+        .iter()
+        .find(|(magic, _)| buf.starts_with(magic))
+        .map(|(_, mime)| mime.to_string())
+}
+
 pub enum GitStatus {
     Untracked,
     Modified,
     Added,
     Deleted,
     Renamed,
-    Copied,
+    TypeChanged,
+    Conflicted,
     Ignored,
     Unmodified,
     Unknown,
@@ -441,7 +574,8 @@ pub fn status_to_friendly_name(status: GitStatus) -> String {
         GitStatus::Added => String::from("added"),
         GitStatus::Deleted => String::from("deleted"),
         GitStatus::Renamed => String::from("renamed"),
-        GitStatus::Copied => String::from("copied"),
+        GitStatus::TypeChanged => String::from("typechange"),
+        GitStatus::Conflicted => String::from("conflicted"),
         GitStatus::Ignored => String::from("ignored"),
         GitStatus::Unmodified => String::from("unmodified"),
         GitStatus::Unknown => String::from("unknown"),
@@ -450,62 +584,236 @@ pub fn status_to_friendly_name(status: GitStatus) -> String {
     }
 }
 
-pub fn path_in_git_repo(path: &Path) -> bool {
-    let git_repo = git2::Repository::discover(path);
-    if git_repo.is_err() {
-        return false;
+/// The worktree (unstaged) half of a raw git status.
+///
+/// `WT_NEW` only ever shows up here if the repo-wide scan that built the
+/// status map was run with `include_untracked(true)` (see
+/// `GitCache::repo_for`) -- otherwise untracked entries never reach this
+/// function and fall back to `GitStatus::Unmodified` at the `status_for`
+/// lookup instead.
+fn worktree_status_from_raw(status: git2::Status) -> GitStatus {
+    if status.contains(git2::Status::CONFLICTED) {
+        GitStatus::Conflicted
+    } else if status.contains(git2::Status::WT_NEW) {
+        GitStatus::Untracked
+    } else if status.contains(git2::Status::WT_MODIFIED) {
+        GitStatus::Modified
+    } else if status.contains(git2::Status::WT_DELETED) {
+        GitStatus::Deleted
+    } else if status.contains(git2::Status::WT_RENAMED) {
+        GitStatus::Renamed
+    } else if status.contains(git2::Status::WT_TYPECHANGE) {
+        GitStatus::TypeChanged
+    } else if status.contains(git2::Status::IGNORED) {
+        GitStatus::Ignored
+    } else {
+        GitStatus::Unmodified
     }
-    true
 }
 
-// TODO: Cache the repos that we have already checked for the sake of speed
-pub fn get_file_git_status(path: &Path) -> Option<GitStatus> {
-    // First check if the file is in a git repo
-    let git_repo = git2::Repository::discover(path);
-    if git_repo.is_err() {
-        return None;
+/// The index (staged) half of a raw git status.
+fn staged_status_from_raw(status: git2::Status) -> GitStatus {
+    if status.contains(git2::Status::CONFLICTED) {
+        GitStatus::Conflicted
+    } else if status.contains(git2::Status::INDEX_NEW) {
+        GitStatus::Added
+    } else if status.contains(git2::Status::INDEX_MODIFIED) {
+        GitStatus::Modified
+    } else if status.contains(git2::Status::INDEX_DELETED) {
+        GitStatus::Deleted
+    } else if status.contains(git2::Status::INDEX_RENAMED) {
+        GitStatus::Renamed
+    } else if status.contains(git2::Status::INDEX_TYPECHANGE) {
+        GitStatus::TypeChanged
+    } else {
+        GitStatus::Unmodified
     }
-    let git_repo = git_repo.expect("This should never be reached, we just checked if the repo was valid");
+}
 
-    let repo_path = match git_repo.workdir() {
-        Some(path) => path,
-        None => return None,
-    };
-    // Now transform the path into a path relative to the repo
-    let relative_path = path.strip_prefix(repo_path).expect("This should never happen, we just checked if the path was a child of the repo");
-
-    let git_status = git_repo.status_file(relative_path);
-    // status_file returns an Ambiguous error if it tried to run on a directory or when the file is ambiguous, checking if the path is a directory is slower but safer
-    if git_status.is_err() {
-        if path.is_dir() {
-            return Some(GitStatus::Directory);
+/// The worktree and index status of a single path, reported separately so a
+/// file that's staged but also modified afterwards doesn't collapse to one
+/// value.
+pub struct EntryGitStatus {
+    pub worktree: GitStatus,
+    pub staged: GitStatus,
+}
+
+/// Repo-level state that's the same for every entry in a repository, so it's
+/// computed once per `RepoCache` rather than per file.
+#[derive(Clone, Default)]
+pub struct RepoGitInfo {
+    pub branch: Option<String>,
+    pub ahead: usize,
+    pub behind: usize,
+    pub stashed: bool,
+}
+
+/// A single repository's status, discovered and scanned once and then shared
+/// by every entry `ls` reports for paths underneath it.
+struct RepoCache {
+    /// Canonicalized working directory of the repository, used to decide
+    /// whether a given path falls under it without re-discovering the repo.
+    workdir: PathBuf,
+    /// Absolute path -> status for every entry `git2` reported as non-current.
+    statuses: HashMap<PathBuf, git2::Status>,
+    /// Current branch, ahead/behind counts, and stash presence for the repo.
+    info: RepoGitInfo,
+}
+
+fn repo_git_info(git_repo: &mut git2::Repository) -> RepoGitInfo {
+    let branch = git_repo
+        .head()
+        .ok()
+        .filter(|head| head.is_branch())
+        .and_then(|head| head.shorthand().map(str::to_string));
+
+    let (ahead, behind) = branch
+        .as_deref()
+        .and_then(|branch_name| {
+            let local_oid = git_repo.head().ok()?.target()?;
+            let upstream_oid = git_repo
+                .find_branch(branch_name, git2::BranchType::Local)
+                .ok()?
+                .upstream()
+                .ok()?
+                .get()
+                .target()?;
+            git_repo.graph_ahead_behind(local_oid, upstream_oid).ok()
+        })
+        .unwrap_or((0, 0));
+
+    let mut stashed = false;
+    let _ = git_repo.stash_foreach(|_, _, _| {
+        stashed = true;
+        false
+    });
+
+    RepoGitInfo {
+        branch,
+        ahead,
+        behind,
+        stashed,
+    }
+}
+
+/// Knobs mirrored from `git2::StatusOptions` so users can shape the status
+/// scan instead of post-filtering a large table (same idea as the toggles
+/// `hg status` exposes for modified/added/removed/untracked/ignored).
+#[derive(Clone, Copy, Default)]
+pub struct GitStatusOptions {
+    pub include_ignored: bool,
+    pub recurse_untracked_dirs: bool,
+    pub detect_renames: bool,
+}
+
+/// Discovers and scans each distinct git repository touched by an `ls`
+/// invocation exactly once, instead of re-discovering the repo and walking
+/// its status for every file. Mirrors the "repo lives for the life of the
+/// program" approach other directory listers use so that many files (or,
+/// eventually, many directories) in the same repo share one scan.
+pub struct GitCache {
+    repos: RefCell<Vec<Arc<RepoCache>>>,
+    options: GitStatusOptions,
+}
+
+impl GitCache {
+    pub fn new(options: GitStatusOptions) -> Self {
+        Self {
+            repos: RefCell::new(Vec::new()),
+            options,
         }
+    }
 
-        match git_status.expect_err("This should never happen, we just made sure that this is an error!").code() {
-            git2::ErrorCode::Ambiguous => return Some(GitStatus::Ambiguous),
-            _ => return Some(GitStatus::Untracked),
+    fn repo_for(&self, path: &Path) -> Option<Arc<RepoCache>> {
+        if let Some(cached) = self
+            .repos
+            .borrow()
+            .iter()
+            .find(|repo| path.starts_with(&repo.workdir))
+        {
+            return Some(cached.clone());
+        }
+
+        let mut git_repo = git2::Repository::discover(path).ok()?;
+        let workdir = git_repo.workdir()?.canonicalize().ok()?;
+
+        let mut status_options = git2::StatusOptions::new();
+        status_options
+            .include_untracked(true)
+            .include_ignored(self.options.include_ignored)
+            .recurse_untracked_dirs(self.options.recurse_untracked_dirs)
+            .renames_head_to_index(self.options.detect_renames)
+            .renames_index_to_workdir(self.options.detect_renames);
+        let statuses = git_repo.statuses(Some(&mut status_options)).ok()?;
+
+        let mut status_map = HashMap::new();
+        for entry in statuses.iter() {
+            if let Some(entry_path) = entry.path() {
+                status_map.insert(workdir.join(entry_path), entry.status());
+            }
         }
+
+        let info = repo_git_info(&mut git_repo);
+
+        let repo_cache = Arc::new(RepoCache {
+            workdir,
+            statuses: status_map,
+            info,
+        });
+        self.repos.borrow_mut().push(repo_cache.clone());
+        Some(repo_cache)
+    }
+
+    /// Whether `path` lives inside a git repository at all.
+    pub fn is_repo(&self, path: &Path) -> bool {
+        self.repo_for(&canonicalize_lossy(path)).is_some()
     }
 
-    let git_status = git_status.expect("This should never happen, we just checked if the status was an error");
+    /// The worktree and index status of `path`, looked up in the cached
+    /// repo-wide scan rather than by asking git about this one file.
+    pub fn status_for(&self, path: &Path) -> Option<EntryGitStatus> {
+        let path = canonicalize_lossy(path);
+        let repo_cache = self.repo_for(&path)?;
+
+        match repo_cache.statuses.get(&path) {
+            Some(status) => Some(EntryGitStatus {
+                worktree: worktree_status_from_raw(*status),
+                staged: staged_status_from_raw(*status),
+            }),
+            // `statuses()` only reports entries that differ from `CURRENT`,
+            // and reports a directory only as a whole untracked or ignored
+            // entry, so anything missing from the map is shown as a
+            // directory or as unmodified. That includes files the scan left
+            // out: ignored files under `--git-no-ignored`, and files inside
+            // an untracked or ignored directory that wasn't recursed into.
+            None if path.is_dir() => Some(EntryGitStatus {
+                worktree: GitStatus::Directory,
+                staged: GitStatus::Directory,
+            }),
+            None => Some(EntryGitStatus {
+                worktree: GitStatus::Unmodified,
+                staged: GitStatus::Unmodified,
+            }),
+        }
+    }
 
-    match git_status {
-        git2::Status::WT_NEW => Some(GitStatus::Added),
-        git2::Status::WT_MODIFIED => Some(GitStatus::Modified),
-        git2::Status::WT_DELETED => Some(GitStatus::Deleted),
-        git2::Status::WT_RENAMED => Some(GitStatus::Renamed),
-        git2::Status::WT_TYPECHANGE => Some(GitStatus::Copied),
-        git2::Status::INDEX_NEW => Some(GitStatus::Added),
-        git2::Status::INDEX_MODIFIED => Some(GitStatus::Modified),
-        git2::Status::INDEX_DELETED => Some(GitStatus::Deleted),
-        git2::Status::INDEX_RENAMED => Some(GitStatus::Renamed),
-        git2::Status::INDEX_TYPECHANGE => Some(GitStatus::Copied),
-        git2::Status::IGNORED => Some(GitStatus::Ignored),
-        git2::Status::CURRENT => Some(GitStatus::Unmodified),
-        _ => Some(GitStatus::Unknown),
+    /// Repo-level info (branch, ahead/behind, stash) for the repo containing
+    /// `path`, computed once when the repo was first scanned.
+    pub fn repo_info_for(&self, path: &Path) -> Option<RepoGitInfo> {
+        Some(self.repo_for(&canonicalize_lossy(path))?.info.clone())
     }
 }
 
+/// `GitCache`'s lookups (the `workdir` prefix check, and the status map keys,
+/// both built from git2's canonicalized `workdir()`) only line up with an
+/// equally canonical path, otherwise a symlinked ancestor (e.g. macOS `/tmp`
+/// -> `/private/tmp`) makes every lookup miss. Falls back to the original
+/// path if it can't be resolved (e.g. a broken symlink) rather than failing
+/// outright.
+fn canonicalize_lossy(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn dir_entry_dict(
     filename: &std::path::Path, // absolute path
@@ -515,12 +823,35 @@ pub(crate) fn dir_entry_dict(
     long: bool,
     du: bool,
     ctrl_c: Option<Arc<AtomicBool>>,
-    use_git: bool,
+    git_cache: Option<&GitCache>,
     use_mime_type: bool,
+    time_zone: &LsTimeZone,
+    full: bool,
 ) -> Result<Value, ShellError> {
+    // `metadata` is `None` when the caller's `symlink_metadata()` call failed
+    // for this one entry (a dangling symlink, a permission hiccup, ...).
+    // Before giving up on the whole row, fall back to the coarser
+    // `fs::metadata()` (which follows symlinks), so any field it can still
+    // answer doesn't get blanked along with the ones it can't.
+    let coarse_metadata;
+    let metadata = match metadata {
+        Some(md) => Some(md),
+        None => {
+            coarse_metadata = std::fs::metadata(filename).ok();
+            coarse_metadata.as_ref()
+        }
+    };
+
     #[cfg(windows)]
     if metadata.is_none() {
-        return windows_helper::dir_entry_dict_windows_fallback(filename, display_name, span, long);
+        return windows_helper::dir_entry_dict_windows_fallback(
+            filename,
+            display_name,
+            span,
+            long,
+            time_zone,
+            full,
+        );
     }
 
     let mut cols = vec![];
@@ -534,7 +865,7 @@ pub(crate) fn dir_entry_dict(
     });
 
     if let Some(md) = metadata {
-        file_type = get_file_type(md, display_name, use_mime_type);
+        file_type = get_file_type(filename, md, display_name, use_mime_type);
         cols.push("type".into());
         vals.push(Value::String {
             val: file_type.clone(),
@@ -545,98 +876,183 @@ pub(crate) fn dir_entry_dict(
         vals.push(Value::nothing(span));
     }
 
-    if use_git && path_in_git_repo(filename) {
-        cols.push("git_status".into());
-        match get_file_git_status(filename) {
-            Some(status) => vals.push(Value::String {
-                val: status_to_friendly_name(status),
-                span,
-            }),
-            None => vals.push(Value::String {
-                val: "error".to_string(),
-                span,
-            }),
-        }
-    }
-
-    if long {
-        cols.push("target".into());
-        if let Some(md) = metadata {
-            if md.file_type().is_symlink() {
-                if let Ok(path_to_link) = filename.read_link() {
+    if let Some(git_cache) = git_cache {
+        if git_cache.is_repo(filename) {
+            match git_cache.status_for(filename) {
+                Some(status) => {
+                    cols.push("git_status".into());
                     vals.push(Value::String {
-                        val: path_to_link.to_string_lossy().to_string(),
+                        val: status_to_friendly_name(status.worktree),
                         span,
                     });
-                } else {
+                    cols.push("git_staged".into());
                     vals.push(Value::String {
-                        val: "Could not obtain target file's path".to_string(),
+                        val: status_to_friendly_name(status.staged),
+                        span,
+                    });
+                }
+                None => {
+                    cols.push("git_status".into());
+                    vals.push(Value::String {
+                        val: "error".to_string(),
                         span,
                     });
                 }
-            } else {
-                vals.push(Value::nothing(span));
             }
-        }
-    }
 
-    if long {
-        if let Some(md) = metadata {
-            cols.push("readonly".into());
-            vals.push(Value::Bool {
-                val: md.permissions().readonly(),
-                span,
-            });
+            if long {
+                let repo_info = git_cache.repo_info_for(filename).unwrap_or_default();
 
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::MetadataExt;
-                let mode = md.permissions().mode();
-                cols.push("mode".into());
-                vals.push(Value::String {
-                    val: umask::Mode::from(mode).to_string(),
-                    span,
+                cols.push("git_branch".into());
+                vals.push(match repo_info.branch {
+                    Some(branch) => Value::String { val: branch, span },
+                    None => Value::nothing(span),
                 });
 
-                let nlinks = md.nlink();
-                cols.push("num_links".into());
+                cols.push("git_ahead".into());
                 vals.push(Value::Int {
-                    val: nlinks as i64,
+                    val: repo_info.ahead as i64,
                     span,
                 });
 
-                let inode = md.ino();
-                cols.push("inode".into());
+                cols.push("git_behind".into());
                 vals.push(Value::Int {
-                    val: inode as i64,
+                    val: repo_info.behind as i64,
                     span,
                 });
 
-                cols.push("uid".into());
-                if let Some(user) = users::get_user_by_uid(md.uid()) {
-                    vals.push(Value::String {
+                cols.push("git_stashed".into());
+                vals.push(Value::Bool {
+                    val: repo_info.stashed,
+                    span,
+                });
+            }
+        }
+    }
+
+    if long {
+        cols.push("target".into());
+        vals.push(match metadata {
+            Some(md) if md.file_type().is_symlink() => match filename.read_link() {
+                Ok(path_to_link) => Value::String {
+                    val: path_to_link.to_string_lossy().to_string(),
+                    span,
+                },
+                Err(_) => Value::String {
+                    val: "Could not obtain target file's path".to_string(),
+                    span,
+                },
+            },
+            Some(_) => Value::nothing(span),
+            None => Value::nothing(span),
+        });
+    }
+
+    if long {
+        cols.push("readonly".into());
+        vals.push(
+            metadata
+                .map(|md| Value::Bool {
+                    val: md.permissions().readonly(),
+                    span,
+                })
+                .unwrap_or_else(|| Value::nothing(span)),
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+
+            cols.push("mode".into());
+            vals.push(
+                metadata
+                    .map(|md| Value::String {
+                        val: umask::Mode::from(md.permissions().mode()).to_string(),
+                        span,
+                    })
+                    .unwrap_or_else(|| Value::nothing(span)),
+            );
+
+            cols.push("num_links".into());
+            vals.push(
+                metadata
+                    .map(|md| Value::Int {
+                        val: md.nlink() as i64,
+                        span,
+                    })
+                    .unwrap_or_else(|| Value::nothing(span)),
+            );
+
+            cols.push("inode".into());
+            vals.push(
+                metadata
+                    .map(|md| Value::Int {
+                        val: md.ino() as i64,
+                        span,
+                    })
+                    .unwrap_or_else(|| Value::nothing(span)),
+            );
+
+            cols.push("uid".into());
+            vals.push(match metadata {
+                Some(md) => match users::get_user_by_uid(md.uid()) {
+                    Some(user) => Value::String {
                         val: user.name().to_string_lossy().into(),
                         span,
-                    });
-                } else {
-                    vals.push(Value::Int {
+                    },
+                    None => Value::Int {
                         val: md.uid() as i64,
                         span,
-                    })
-                }
+                    },
+                },
+                None => Value::nothing(span),
+            });
 
-                cols.push("group".into());
-                if let Some(group) = users::get_group_by_gid(md.gid()) {
-                    vals.push(Value::String {
+            cols.push("group".into());
+            vals.push(match metadata {
+                Some(md) => match users::get_group_by_gid(md.gid()) {
+                    Some(group) => Value::String {
                         val: group.name().to_string_lossy().into(),
                         span,
-                    });
-                } else {
-                    vals.push(Value::Int {
+                    },
+                    None => Value::Int {
                         val: md.gid() as i64,
                         span,
-                    })
-                }
+                    },
+                },
+                None => Value::nothing(span),
+            });
+
+            if full {
+                cols.push("mode_octal".into());
+                vals.push(
+                    metadata
+                        .map(|md| Value::String {
+                            val: format!("{:o}", md.permissions().mode() & 0o7777),
+                            span,
+                        })
+                        .unwrap_or_else(|| Value::nothing(span)),
+                );
+
+                cols.push("device".into());
+                vals.push(
+                    metadata
+                        .map(|md| Value::Int {
+                            val: md.dev() as i64,
+                            span,
+                        })
+                        .unwrap_or_else(|| Value::nothing(span)),
+                );
+
+                cols.push("blocks".into());
+                vals.push(
+                    metadata
+                        .map(|md| Value::Int {
+                            val: md.blocks() as i64,
+                            span,
+                        })
+                        .unwrap_or_else(|| Value::nothing(span)),
+                );
             }
         }
     }
@@ -694,47 +1110,56 @@ pub(crate) fn dir_entry_dict(
     if let Some(md) = metadata {
         if long {
             cols.push("created".to_string());
+            vals.push(
+                md.created()
+                    .ok()
+                    .map(|c| date_value(c, span, time_zone))
+                    .unwrap_or_else(|| Value::nothing(span)),
+            );
+
+            cols.push("accessed".to_string());
+            vals.push(
+                md.accessed()
+                    .ok()
+                    .map(|a| date_value(a, span, time_zone))
+                    .unwrap_or_else(|| Value::nothing(span)),
+            );
+
+            #[cfg(unix)]
             {
-                let mut val = Value::nothing(span);
-                if let Ok(c) = md.created() {
-                    if let Some(local) = try_convert_to_local_date_time(c) {
-                        val = Value::Date {
-                            val: local.with_timezone(local.offset()),
-                            span,
-                        };
-                    }
-                }
-                vals.push(val);
+                cols.push("changed".to_string());
+                vals.push(
+                    unix_change_time(filename)
+                        .map(|(secs, nsecs)| date_value_from_epoch(secs, nsecs, span, time_zone))
+                        .unwrap_or_else(|| Value::nothing(span)),
+                );
             }
 
-            cols.push("accessed".to_string());
+            // `std::fs::Metadata` has no inode status-change time on
+            // Windows; reuse `created()` here too, for the same reason the
+            // `FindFirstFileW` fallback reuses `ftCreationTime` -- keeps the
+            // `changed` column present (if only approximate) on every row,
+            // instead of appearing only on the entries that hit the
+            // fallback path.
+            #[cfg(windows)]
             {
-                let mut val = Value::nothing(span);
-                if let Ok(a) = md.accessed() {
-                    if let Some(local) = try_convert_to_local_date_time(a) {
-                        val = Value::Date {
-                            val: local.with_timezone(local.offset()),
-                            span,
-                        };
-                    }
-                }
-                vals.push(val);
+                cols.push("changed".to_string());
+                vals.push(
+                    md.created()
+                        .ok()
+                        .map(|c| date_value(c, span, time_zone))
+                        .unwrap_or_else(|| Value::nothing(span)),
+                );
             }
         }
 
         cols.push("modified".to_string());
-        {
-            let mut val = Value::nothing(span);
-            if let Ok(m) = md.modified() {
-                if let Some(local) = try_convert_to_local_date_time(m) {
-                    val = Value::Date {
-                        val: local.with_timezone(local.offset()),
-                        span,
-                    };
-                }
-            }
-            vals.push(val);
-        }
+        vals.push(
+            md.modified()
+                .ok()
+                .map(|m| date_value(m, span, time_zone))
+                .unwrap_or_else(|| Value::nothing(span)),
+        );
     } else {
         if long {
             cols.push("created".to_string());
@@ -742,6 +1167,18 @@ pub(crate) fn dir_entry_dict(
 
             cols.push("accessed".to_string());
             vals.push(Value::nothing(span));
+
+            #[cfg(unix)]
+            {
+                cols.push("changed".to_string());
+                vals.push(Value::nothing(span));
+            }
+
+            #[cfg(windows)]
+            {
+                cols.push("changed".to_string());
+                vals.push(Value::nothing(span));
+            }
         }
 
         cols.push("modified".to_string());
@@ -751,9 +1188,11 @@ pub(crate) fn dir_entry_dict(
     Ok(Value::Record { cols, vals, span })
 }
 
-// TODO: can we get away from local times in `ls`? internals might be cleaner if we worked in UTC
-// and left the conversion to local time to the display layer
-fn try_convert_to_local_date_time(t: SystemTime) -> Option<DateTime<Local>> {
+/// Converts a `SystemTime` to a UTC instant, keeping sub-second precision.
+/// It shares `epoch_to_utc` with the raw `(seconds, nanoseconds)` timestamps
+/// read from `lstat` and `FILETIME`; the user's chosen timezone is applied
+/// only at the very end.
+fn system_time_to_utc(t: SystemTime) -> Option<DateTime<Utc>> {
     // Adapted from https://github.com/chronotope/chrono/blob/v0.4.19/src/datetime.rs#L755-L767.
     let (sec, nsec) = match t.duration_since(UNIX_EPOCH) {
         Ok(dur) => (dur.as_secs() as i64, dur.subsec_nanos()),
@@ -769,21 +1208,65 @@ fn try_convert_to_local_date_time(t: SystemTime) -> Option<DateTime<Local>> {
         }
     };
 
-    match Utc.timestamp_opt(sec, nsec) {
-        LocalResult::Single(t) => Some(t.with_timezone(&Local)),
+    epoch_to_utc(sec, nsec)
+}
+
+fn epoch_to_utc(secs: i64, nsecs: u32) -> Option<DateTime<Utc>> {
+    match Utc.timestamp_opt(secs, nsecs) {
+        LocalResult::Single(t) => Some(t),
         _ => None,
     }
 }
 
-// #[cfg(windows)] is just to make Clippy happy, remove if you ever want to use this on other platforms
-#[cfg(windows)]
-fn unix_time_to_local_date_time(secs: i64) -> Option<DateTime<Local>> {
-    match Utc.timestamp_opt(secs, 0) {
-        LocalResult::Single(t) => Some(t.with_timezone(&Local)),
-        _ => None,
+/// Applies the user's chosen timezone to a UTC instant, right before it's
+/// handed off to `Value::Date` (which wants a fixed offset).
+fn apply_time_zone(utc: DateTime<Utc>, time_zone: &LsTimeZone) -> DateTime<FixedOffset> {
+    match time_zone {
+        LsTimeZone::Utc => utc.fixed_offset(),
+        LsTimeZone::Local => utc.with_timezone(&Local).fixed_offset(),
+        LsTimeZone::Named(zone) => utc.with_timezone(zone).fixed_offset(),
     }
 }
 
+fn date_value(t: SystemTime, span: Span, time_zone: &LsTimeZone) -> Value {
+    match system_time_to_utc(t) {
+        Some(utc) => Value::Date {
+            val: apply_time_zone(utc, time_zone),
+            span,
+        },
+        None => Value::nothing(span),
+    }
+}
+
+fn date_value_from_epoch(secs: i64, nsecs: u32, span: Span, time_zone: &LsTimeZone) -> Value {
+    match epoch_to_utc(secs, nsecs) {
+        Some(utc) => Value::Date {
+            val: apply_time_zone(utc, time_zone),
+            span,
+        },
+        None => Value::nothing(span),
+    }
+}
+
+/// The inode status-change time (`st_ctime`), which `std::fs::Metadata`
+/// doesn't expose. This is the timestamp git uses to detect metadata-only
+/// changes (permission/ownership edits that don't touch `mtime`), so `lstat`
+/// is called directly to get at it. Returns `(seconds, nanoseconds)` since
+/// the Unix epoch so the caller can apply the chosen timezone.
+#[cfg(unix)]
+fn unix_change_time(path: &Path) -> Option<(i64, u32)> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat_buf: libc::stat = unsafe { std::mem::zeroed() };
+    if unsafe { libc::lstat(c_path.as_ptr(), &mut stat_buf) } != 0 {
+        return None;
+    }
+
+    Some((stat_buf.st_ctime, stat_buf.st_ctime_nsec as u32))
+}
+
 #[cfg(windows)]
 mod windows_helper {
     use super::*;
@@ -792,8 +1275,10 @@ mod windows_helper {
     use std::os::windows::prelude::OsStrExt;
     use windows::Win32::Foundation::FILETIME;
     use windows::Win32::Storage::FileSystem::{
-        FindFirstFileW, FILE_ATTRIBUTE_DIRECTORY, FILE_ATTRIBUTE_READONLY,
-        FILE_ATTRIBUTE_REPARSE_POINT, WIN32_FIND_DATAW,
+        FindFirstFileW, FILE_ATTRIBUTE_ARCHIVE, FILE_ATTRIBUTE_COMPRESSED,
+        FILE_ATTRIBUTE_DIRECTORY, FILE_ATTRIBUTE_ENCRYPTED, FILE_ATTRIBUTE_HIDDEN,
+        FILE_ATTRIBUTE_READONLY, FILE_ATTRIBUTE_REPARSE_POINT, FILE_ATTRIBUTE_SYSTEM,
+        FILE_ATTRIBUTE_TEMPORARY, WIN32_FIND_DATAW,
     };
     use windows::Win32::System::SystemServices::{
         IO_REPARSE_TAG_MOUNT_POINT, IO_REPARSE_TAG_SYMLINK,
@@ -802,11 +1287,18 @@ mod windows_helper {
     /// A secondary way to get file info on Windows, for when std::fs::symlink_metadata() fails.
     /// dir_entry_dict depends on metadata, but that can't be retrieved for some Windows system files:
     /// https://github.com/rust-lang/rust/issues/96980
+    ///
+    /// `FindFirstFileW` itself can also fail for a given entry (e.g. an
+    /// illegal filename); when it does, this still returns a row with
+    /// `name` filled in and every other field marked `nothing`, rather than
+    /// erroring out the whole entry.
     pub fn dir_entry_dict_windows_fallback(
         filename: &Path,
         display_name: &str,
         span: Span,
         long: bool,
+        time_zone: &LsTimeZone,
+        full: bool,
     ) -> Result<Value, ShellError> {
         let mut cols = vec![];
         let mut vals = vec![];
@@ -817,91 +1309,130 @@ mod windows_helper {
             span,
         });
 
-        let find_data = find_first_file(filename, span)?;
+        let find_data = find_first_file(filename, span).ok();
 
         cols.push("type".into());
-        vals.push(Value::String {
-            val: get_file_type_windows_fallback(&find_data),
-            span,
-        });
+        vals.push(
+            find_data
+                .as_ref()
+                .map(|fd| Value::String {
+                    val: get_file_type_windows_fallback(fd),
+                    span,
+                })
+                .unwrap_or_else(|| Value::nothing(span)),
+        );
 
         if long {
             cols.push("target".into());
-            if is_symlink(&find_data) {
-                if let Ok(path_to_link) = filename.read_link() {
-                    vals.push(Value::String {
+            vals.push(match &find_data {
+                Some(fd) if is_symlink(fd) => match filename.read_link() {
+                    Ok(path_to_link) => Value::String {
                         val: path_to_link.to_string_lossy().to_string(),
                         span,
-                    });
-                } else {
-                    vals.push(Value::String {
+                    },
+                    Err(_) => Value::String {
                         val: "Could not obtain target file's path".to_string(),
                         span,
-                    });
-                }
-            } else {
-                vals.push(Value::nothing(span));
-            }
+                    },
+                },
+                Some(_) => Value::nothing(span),
+                None => Value::nothing(span),
+            });
 
             cols.push("readonly".into());
-            vals.push(Value::Bool {
-                val: (find_data.dwFileAttributes & FILE_ATTRIBUTE_READONLY.0 != 0),
-                span,
-            });
+            vals.push(
+                find_data
+                    .as_ref()
+                    .map(|fd| Value::Bool {
+                        val: fd.dwFileAttributes & FILE_ATTRIBUTE_READONLY.0 != 0,
+                        span,
+                    })
+                    .unwrap_or_else(|| Value::nothing(span)),
+            );
+
+            if full {
+                cols.push("attributes".into());
+                vals.push(
+                    find_data
+                        .as_ref()
+                        .map(|fd| Value::String {
+                            val: decode_file_attributes(fd.dwFileAttributes),
+                            span,
+                        })
+                        .unwrap_or_else(|| Value::nothing(span)),
+                );
+            }
         }
 
         cols.push("size".to_string());
-        let file_size = (find_data.nFileSizeHigh as u64) << 32 | find_data.nFileSizeLow as u64;
-        vals.push(Value::Filesize {
-            val: file_size as i64,
-            span,
-        });
+        vals.push(
+            find_data
+                .as_ref()
+                .map(|fd| {
+                    let file_size = (fd.nFileSizeHigh as u64) << 32 | fd.nFileSizeLow as u64;
+                    Value::Filesize {
+                        val: file_size as i64,
+                        span,
+                    }
+                })
+                .unwrap_or_else(|| Value::nothing(span)),
+        );
 
         if long {
             cols.push("created".to_string());
-            {
-                let mut val = Value::nothing(span);
-                let seconds_since_unix_epoch = unix_time_from_filetime(&find_data.ftCreationTime);
-                if let Some(local) = unix_time_to_local_date_time(seconds_since_unix_epoch) {
-                    val = Value::Date {
-                        val: local.with_timezone(local.offset()),
-                        span,
-                    };
-                }
-                vals.push(val);
-            }
+            vals.push(
+                find_data
+                    .as_ref()
+                    .map(|fd| {
+                        let (secs, nsecs) = unix_time_from_filetime(&fd.ftCreationTime);
+                        date_value_from_epoch(secs, nsecs, span, time_zone)
+                    })
+                    .unwrap_or_else(|| Value::nothing(span)),
+            );
 
             cols.push("accessed".to_string());
-            {
-                let mut val = Value::nothing(span);
-                let seconds_since_unix_epoch = unix_time_from_filetime(&find_data.ftLastAccessTime);
-                if let Some(local) = unix_time_to_local_date_time(seconds_since_unix_epoch) {
-                    val = Value::Date {
-                        val: local.with_timezone(local.offset()),
-                        span,
-                    };
-                }
-                vals.push(val);
-            }
+            vals.push(
+                find_data
+                    .as_ref()
+                    .map(|fd| {
+                        let (secs, nsecs) = unix_time_from_filetime(&fd.ftLastAccessTime);
+                        date_value_from_epoch(secs, nsecs, span, time_zone)
+                    })
+                    .unwrap_or_else(|| Value::nothing(span)),
+            );
+
+            // Windows has no real inode status-change time; `ftCreationTime`
+            // is the closest thing FindFirstFileW exposes, so it's reused
+            // here to keep the `changed` column present on both platforms.
+            cols.push("changed".to_string());
+            vals.push(
+                find_data
+                    .as_ref()
+                    .map(|fd| {
+                        let (secs, nsecs) = unix_time_from_filetime(&fd.ftCreationTime);
+                        date_value_from_epoch(secs, nsecs, span, time_zone)
+                    })
+                    .unwrap_or_else(|| Value::nothing(span)),
+            );
         }
 
         cols.push("modified".to_string());
-        {
-            let mut val = Value::nothing(span);
-            let seconds_since_unix_epoch = unix_time_from_filetime(&find_data.ftLastWriteTime);
-            if let Some(local) = unix_time_to_local_date_time(seconds_since_unix_epoch) {
-                val = Value::Date {
-                    val: local.with_timezone(local.offset()),
-                    span,
-                };
-            }
-            vals.push(val);
-        }
+        vals.push(
+            find_data
+                .as_ref()
+                .map(|fd| {
+                    let (secs, nsecs) = unix_time_from_filetime(&fd.ftLastWriteTime);
+                    date_value_from_epoch(secs, nsecs, span, time_zone)
+                })
+                .unwrap_or_else(|| Value::nothing(span)),
+        );
 
         Ok(Value::Record { cols, vals, span })
     }
 
-    fn unix_time_from_filetime(ft: &FILETIME) -> i64 {
+    /// Returns `(seconds, nanoseconds)` since the Unix epoch, keeping the
+    /// sub-second remainder that used to be discarded by integer division.
+    fn unix_time_from_filetime(ft: &FILETIME) -> (i64, u32) {
         /// January 1, 1970 as Windows file time
         const EPOCH_AS_FILETIME: u64 = 116444736000000000;
         const HUNDREDS_OF_NANOSECONDS: u64 = 10000000;
@@ -909,8 +1440,9 @@ mod windows_helper {
         let time_u64 = ((ft.dwHighDateTime as u64) << 32) | (ft.dwLowDateTime as u64);
         let rel_to_linux_epoch = time_u64 - EPOCH_AS_FILETIME;
         let seconds_since_unix_epoch = rel_to_linux_epoch / HUNDREDS_OF_NANOSECONDS;
+        let nanos = (rel_to_linux_epoch % HUNDREDS_OF_NANOSECONDS) * 100;
 
-        seconds_since_unix_epoch as i64
+        (seconds_since_unix_epoch as i64, nanos as u32)
     }
 
     // wrapper around the FindFirstFileW Win32 API
@@ -968,4 +1500,28 @@ mod windows_helper {
         }
         false
     }
+
+    /// Decodes the `dwFileAttributes` bitmask into its named flags, the
+    /// closest Windows analogue to the Unix `mode`/`blocks`/`device` columns
+    /// that `--full` adds on Unix.
+    fn decode_file_attributes(attributes: u32) -> String {
+        const FLAGS: &[(u32, &str)] = &[
+            (FILE_ATTRIBUTE_READONLY.0, "readonly"),
+            (FILE_ATTRIBUTE_HIDDEN.0, "hidden"),
+            (FILE_ATTRIBUTE_SYSTEM.0, "system"),
+            (FILE_ATTRIBUTE_DIRECTORY.0, "directory"),
+            (FILE_ATTRIBUTE_ARCHIVE.0, "archive"),
+            (FILE_ATTRIBUTE_TEMPORARY.0, "temporary"),
+            (FILE_ATTRIBUTE_COMPRESSED.0, "compressed"),
+            (FILE_ATTRIBUTE_ENCRYPTED.0, "encrypted"),
+            (FILE_ATTRIBUTE_REPARSE_POINT.0, "reparse-point"),
+        ];
+
+        FLAGS
+            .iter()
+            .filter(|(flag, _)| attributes & flag != 0)
+            .map(|(_, name)| *name)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
 }